@@ -8,15 +8,28 @@ const rugDetector = require('../services/rugDetector');
 const { getPaymentTracker } = require('../services/paymentTracker');
 const x402 = require('../services/x402');
 const zkml = require('../services/zkmlProver');
+const policy = require('../services/policy');
+const { getDenylist } = require('../services/denylist');
+
+// Limits for opaque integrator metadata (request body itself is capped at 1kb)
+const CLIENT_METADATA_MAX_KEYS = 16;
+const CLIENT_METADATA_MAX_BYTES = 512;
 
 // Initialize payment tracker
 const paymentTracker = getPaymentTracker();
 
+// Load risk policy up front so a broken config stops the server at startup,
+// not after a caller's payment has been consumed
+policy.loadPolicy();
+
+// Initialize denylist (loads and refreshes curated scam lists)
+const denylist = getDenylist();
+
 // POST /check - Analyze contract for rug pull risk
 router.post('/', async (req, res) => {
   try {
     // Extract request parameters
-    let { payment_id, contract_address, blockchain = 'base' } = req.body;
+    let { payment_id, contract_address, blockchain = 'base', threshold, client_metadata } = req.body;
 
     // Validate contract_address first
     if (!contract_address) {
@@ -79,6 +92,38 @@ router.post('/', async (req, res) => {
       });
     }
 
+    // Validate consumer decision threshold (optional)
+    if (threshold !== undefined) {
+      const thresholdError = policy.validateThreshold(threshold);
+      if (thresholdError) {
+        return res.status(422).json({
+          success: false,
+          error: thresholdError
+        });
+      }
+    }
+
+    // Validate client metadata (optional, echoed back untouched, never part of the proof)
+    if (client_metadata !== undefined) {
+      const isPlainObject = client_metadata !== null && typeof client_metadata === 'object' && !Array.isArray(client_metadata);
+      const entries = isPlainObject ? Object.entries(client_metadata) : [];
+
+      if (!isPlainObject || entries.some(([, value]) => typeof value !== 'string')) {
+        return res.status(422).json({
+          success: false,
+          error: 'Invalid client_metadata. Expected an object with string values.'
+        });
+      }
+
+      if (entries.length > CLIENT_METADATA_MAX_KEYS ||
+          Buffer.byteLength(JSON.stringify(client_metadata)) > CLIENT_METADATA_MAX_BYTES) {
+        return res.status(422).json({
+          success: false,
+          error: `Invalid client_metadata. At most ${CLIENT_METADATA_MAX_KEYS} keys and ${CLIENT_METADATA_MAX_BYTES} bytes allowed.`
+        });
+      }
+    }
+
     // Check for demo mode
     const isDemoMode = payment_id.toLowerCase().startsWith('demo_');
 
@@ -178,16 +223,17 @@ router.post('/', async (req, res) => {
 
     // Step 2: Extract features from contract
     console.log(`[Check] Extracting features from ${contract_address} on ${blockchain}`);
-    let features, zkmlFeatures;
+    let features, zkmlFeatures, creator, imputed, imputationReason;
+    let provenance = null;  // Only tracked for the zkML feature mapping
     try {
       // Use zkML model if enabled
       if (rugDetector.USE_ZKML_MODEL) {
         console.log(`[Check] Using zkML model (18 features)`);
-        zkmlFeatures = await rugDetector.extractZkmlFeatures(contract_address, blockchain);
+        ({ features: zkmlFeatures, provenance, creator, imputed, imputationReason } = await rugDetector.extractZkmlFeatures(contract_address, blockchain));
         console.log(`[Check] Extracted ${zkmlFeatures.length} zkML features`);
         features = zkmlFeatures;  // For backward compatibility
       } else {
-        features = await rugDetector.extractFeatures(contract_address, blockchain);
+        ({ features, creator, imputed, imputationReason } = await rugDetector.extractFeatures(contract_address, blockchain));
         console.log(`[Check] Extracted ${Object.keys(features).length} features`);
       }
     } catch (extractError) {
@@ -216,17 +262,53 @@ router.post('/', async (req, res) => {
       });
     }
 
-    // Step 4: Generate recommendation
+    // Step 4: Apply risk policy to model output
+    let decision;
+    let denylistMatches;
+    let deployerChecked = null;  // null when no deployer lists are configured
+    try {
+      denylistMatches = await denylist.check(contract_address, creator);
+      if (denylistMatches.length > 0) {
+        console.log(`[Check] ⚠️ Denylist match: ${denylistMatches.map(m => m.list).join(', ')}`);
+      }
+      if (denylist.hasDeployerLists() || policy.loadPolicy().deployerDenylist.size > 0) {
+        deployerChecked = Boolean(creator);
+        if (!deployerChecked) {
+          console.warn('[Check] Deployer unknown, deployer denylists were not checked');
+        }
+      }
+      decision = policy.applyPolicy(analysis, {
+        contractAddress: contract_address,
+        blockchain,
+        creator,
+        denylistMatches,
+        threshold: threshold !== undefined ? threshold : null
+      });
+      console.log(`[Check] Policy ${decision.version}: ${decision.riskCategory} risk (rule: ${decision.rule})`);
+    } catch (policyError) {
+      console.error('[Check] Policy error:', policyError.message);
+      return res.status(500).json({
+        success: false,
+        error: `Policy evaluation failed: ${policyError.message}`
+      });
+    }
+
+    // Step 5: Generate recommendation
     let recommendation;
-    if (analysis.riskCategory === 'high') {
+    if (decision.riskCategory === 'high') {
       recommendation = 'High risk detected. Avoid investing. Multiple red flags identified.';
-    } else if (analysis.riskCategory === 'medium') {
+    } else if (decision.riskCategory === 'medium') {
       recommendation = 'Medium risk detected. Proceed with caution and conduct thorough research.';
+    } else if (decision.riskCategory === 'inconclusive') {
+      recommendation = 'Inconclusive. The model is not confident enough for a verdict on this contract. Conduct your own research.';
     } else {
       recommendation = 'Low risk detected. Contract appears relatively safe, but always DYOR.';
     }
+    if (imputed) {
+      recommendation += ' Warning: on-chain data could not be read, so this result is based on imputed feature values.';
+    }
 
-    // Step 5: Generate zkML proof
+    // Step 6: Generate zkML proof
     console.log(`[Check] Generating zkML proof`);
     let zkmlProof;
     try {
@@ -244,7 +326,7 @@ router.post('/', async (req, res) => {
       };
     }
 
-    // Step 6: Verify zkML proof locally
+    // Step 7: Verify zkML proof locally
     let verification = { valid: false, reason: 'Not verified' };
     if (zkmlProof && zkmlProof.proof_id !== 'unavailable' && zkmlProof.proof_id !== 'error') {
       console.log(`[Check] Verifying zkML proof ${zkmlProof.proof_id.slice(0, 16)}...`);
@@ -276,12 +358,35 @@ router.post('/', async (req, res) => {
       data: {
         contract_address,
         blockchain,
+        deployer: creator,
         riskScore: analysis.riskScore,
-        riskCategory: analysis.riskCategory,
+        riskCategory: decision.riskCategory,
         confidence: analysis.confidence,
+        model: {
+          riskScore: analysis.riskScore,
+          riskCategory: analysis.riskCategory,
+          confidence: analysis.confidence
+        },
+        policy: {
+          version: decision.version,
+          rule: decision.rule,
+          uncertainty_band: decision.uncertaintyBand,
+          threshold: decision.threshold,
+          consumer_adjustment: decision.consumerAdjustment
+        },
+        denylist: {
+          matched: denylistMatches.length > 0,
+          matches: denylistMatches,
+          deployer_checked: deployerChecked,
+          lists: denylist.getVersions()
+        },
         features: features,
+        feature_provenance: provenance,
+        features_imputed: imputed,
+        imputation_reason: imputationReason,
         recommendation: recommendation,
         analysis_timestamp: new Date().toISOString(),
+        client_metadata: client_metadata !== undefined ? client_metadata : null,
         zkml: zkmlProof
       }
     });
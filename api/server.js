@@ -5,6 +5,7 @@ const express = require('express');
 const cors = require('cors');
 const dotenv = require('dotenv');
 const path = require('path');
+const fs = require('fs');
 const rateLimit = require('express-rate-limit');
 
 // Load environment variables
@@ -76,6 +77,22 @@ app.use(globalLimiter); // Apply global rate limiting
 // Serve UI static files
 app.use(express.static(path.join(__dirname, '../ui')));
 
+// Service descriptor: fill in the threshold bounds from the active risk policy
+// so the published schema always matches what /check enforces
+const serviceDescriptor = JSON.parse(fs.readFileSync(path.join(__dirname, '../public/.well-known/ai-service.json'), 'utf8'));
+const { consumerThreshold } = require('./services/policy').loadPolicy();
+const checkEndpoint = (serviceDescriptor.endpoints || []).find(endpoint => endpoint.path === '/check');
+const thresholdSchema = checkEndpoint && checkEndpoint.input && checkEndpoint.input.properties && checkEndpoint.input.properties.threshold;
+if (thresholdSchema) {
+  thresholdSchema.minimum = consumerThreshold.min;
+  thresholdSchema.maximum = consumerThreshold.max;
+} else {
+  console.warn('⚠️  ai-service.json has no /check threshold input; publishing it unchanged');
+}
+app.get('/.well-known/ai-service.json', (req, res) => {
+  res.json(serviceDescriptor);
+});
+
 // Serve .well-known directory for X402 service discovery
 app.use('/.well-known', express.static(path.join(__dirname, '../public/.well-known')));
 
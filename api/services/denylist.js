@@ -0,0 +1,243 @@
+/**
+ * Denylist Service
+ * Loads curated lists of known scam deployers and rug-associated contracts
+ *
+ * Sources are local files or HTTP(S) URLs and are refreshed periodically.
+ * Each list is identified by the SHA-256 of its content so reports can
+ * record exactly which list version produced a match.
+ */
+
+const fs = require('fs').promises;
+const path = require('path');
+const crypto = require('crypto');
+const { normalizeAddress } = require('./policy');
+
+// Longest a request waits for the initial list load before checking what is loaded
+const FIRST_LOAD_WAIT_MS = 2000;
+
+class Denylist {
+  constructor(sources = [], refreshInterval = 3600000, fetchTimeout = 10000) { // Default: refresh every hour
+    this.sources = sources;
+    this.fetchTimeout = fetchTimeout;
+    this.lists = new Map(); // source -> { name, type, version, addresses, loadedAt }
+    this.loading = null;
+    this.refreshing = null;
+
+    if (this.sources.length > 0) {
+      this.loading = this.refresh();
+      this.refreshInterval = setInterval(() => {
+        if (this.refreshing) {
+          console.warn('[Denylist] Previous refresh still running, skipping this tick');
+          return;
+        }
+        this.refresh();
+      }, refreshInterval);
+      // Don't keep the process alive just for list refreshes
+      this.refreshInterval.unref();
+    }
+
+    console.log('[Denylist] Initialized with', this.sources.length, 'source(s)');
+  }
+
+  /**
+   * Fetch raw list content from a URL or file path
+   * @param {string} source - URL or path (relative paths resolve from the repo root)
+   * @returns {Promise<string>}
+   */
+  async fetchSource(source) {
+    if (/^https?:\/\//i.test(source)) {
+      const response = await fetch(source, { signal: AbortSignal.timeout(this.fetchTimeout) });
+      if (!response.ok) {
+        throw new Error(`HTTP ${response.status}`);
+      }
+      return response.text();
+    }
+    return fs.readFile(path.resolve(__dirname, '../..', source), 'utf8');
+  }
+
+  /**
+   * Parse list content
+   * Accepts JSON ({ name, type, addresses: [...] } or a bare array) or plain text
+   * with one address per line and # comments. type is 'contract' (default)
+   * or 'deployer'; only JSON lists can be deployer lists
+   * @param {string} source - Where the content came from (default list name)
+   * @param {string} content - Raw content
+   * @returns {{name: string, type: string, addresses: Array<string>}}
+   */
+  parseList(source, content) {
+    const trimmed = content.trim();
+    if (trimmed.startsWith('{') || trimmed.startsWith('[')) {
+      const data = JSON.parse(trimmed);
+      const addresses = Array.isArray(data) ? data : data.addresses;
+      if (!Array.isArray(addresses)) {
+        throw new Error('JSON list must be an array or have an "addresses" array');
+      }
+      const type = (!Array.isArray(data) && data.type) || 'contract';
+      if (type !== 'contract' && type !== 'deployer') {
+        throw new Error(`Unknown list type "${type}", expected contract or deployer`);
+      }
+      return { name: (!Array.isArray(data) && data.name) || source, type, addresses };
+    }
+
+    const addresses = trimmed.split('\n')
+      .map(line => line.replace(/#.*/, '').trim())
+      .filter(line => line.length > 0);
+    return { name: source, type: 'contract', addresses };
+  }
+
+  /**
+   * Reload all sources
+   * A source that fails to load keeps its previously loaded version
+   * @returns {Promise<void>} the in-flight refresh, if one is already running
+   */
+  refresh() {
+    if (!this.refreshing) {
+      this.refreshing = this.loadSources().finally(() => {
+        this.refreshing = null;
+      });
+    }
+    return this.refreshing;
+  }
+
+  /**
+   * Fetch and parse every source once
+   */
+  async loadSources() {
+    await Promise.all(this.sources.map(async (source) => {
+      try {
+        const content = await this.fetchSource(source);
+        const version = crypto.createHash('sha256').update(content).digest('hex');
+
+        const current = this.lists.get(source);
+        if (current && current.version === version) {
+          return;
+        }
+
+        const { name, type, addresses } = this.parseList(source, content);
+        this.lists.set(source, {
+          name,
+          type,
+          version,
+          addresses: new Set(addresses.map(normalizeAddress)),
+          loadedAt: new Date().toISOString()
+        });
+        console.log(`[Denylist] Loaded ${addresses.length} ${type} addresses from ${name} (${version.slice(0, 16)})`);
+      } catch (error) {
+        console.error(`[Denylist] Failed to load ${source}:`, error.message);
+      }
+    }));
+  }
+
+  /**
+   * Look up a contract and its deployer in all loaded lists
+   * Contract lists are matched against the contract, deployer lists against the creator
+   * @param {string} address - Contract address
+   * @param {?string} creator - Deployer address, if extraction found one
+   * @returns {Promise<Array<{list: string, type: string, version: string}>>} matching lists
+   */
+  async check(address, creator = null) {
+    if (this.loading) {
+      // Don't hold requests hostage to a slow list host; check what is loaded so far
+      let timer;
+      const settled = await Promise.race([
+        this.loading.then(() => true),
+        new Promise(resolve => { timer = setTimeout(() => resolve(false), FIRST_LOAD_WAIT_MS); })
+      ]);
+      clearTimeout(timer);
+      if (settled) {
+        this.loading = null;
+      } else {
+        console.warn('[Denylist] Initial load still running, checking loaded lists only');
+      }
+    }
+
+    const normalized = normalizeAddress(address);
+    const normalizedCreator = creator ? normalizeAddress(creator) : null;
+    const matches = [];
+    for (const list of this.lists.values()) {
+      const target = list.type === 'deployer' ? normalizedCreator : normalized;
+      if (target && list.addresses.has(target)) {
+        matches.push({ list: list.name, type: list.type, version: list.version });
+      }
+    }
+    return matches;
+  }
+
+  /**
+   * Whether any loaded list is matched against deployers
+   * @returns {boolean}
+   */
+  hasDeployerLists() {
+    return Array.from(this.lists.values()).some(list => list.type === 'deployer');
+  }
+
+  /**
+   * Versions of all currently loaded lists
+   * @returns {Array<{list: string, type: string, version: string, size: number, loaded_at: string}>}
+   */
+  getVersions() {
+    return Array.from(this.lists.values()).map(list => ({
+      list: list.name,
+      type: list.type,
+      version: list.version,
+      size: list.addresses.size,
+      loaded_at: list.loadedAt
+    }));
+  }
+
+  /**
+   * Stop periodic refreshes
+   */
+  shutdown() {
+    if (this.refreshInterval) {
+      clearInterval(this.refreshInterval);
+      this.refreshInterval = null;
+    }
+    console.log('[Denylist] Shutdown complete');
+  }
+}
+
+// Singleton instance
+let denylistInstance = null;
+
+/**
+ * Parse a positive number of seconds from the environment
+ * @param {string} name - Environment variable
+ * @param {number} fallback - Seconds to use when unset or invalid
+ * @returns {number} milliseconds
+ */
+function secondsFromEnv(name, fallback) {
+  const raw = process.env[name];
+  if (raw === undefined || raw === '') {
+    return fallback * 1000;
+  }
+  const seconds = Number(raw);
+  if (!Number.isFinite(seconds) || seconds <= 0) {
+    console.warn(`[Denylist] Invalid ${name}=${raw}, using ${fallback}s`);
+    return fallback * 1000;
+  }
+  return seconds * 1000;
+}
+
+/**
+ * Get or create the denylist instance
+ * Sources come from DENYLIST_SOURCES (comma-separated URLs or paths)
+ * @returns {Denylist}
+ */
+function getDenylist() {
+  if (!denylistInstance) {
+    const sources = (process.env.DENYLIST_SOURCES || '')
+      .split(',')
+      .map(source => source.trim())
+      .filter(source => source.length > 0);
+    const refreshMs = secondsFromEnv('DENYLIST_REFRESH_SECONDS', 3600);
+    const fetchTimeoutMs = secondsFromEnv('DENYLIST_FETCH_TIMEOUT_SECONDS', 10);
+    denylistInstance = new Denylist(sources, refreshMs, fetchTimeoutMs);
+  }
+  return denylistInstance;
+}
+
+module.exports = {
+  Denylist,
+  getDenylist
+};
@@ -0,0 +1,225 @@
+// Risk Policy Service
+// Turns raw model output into the final product classification
+
+const fs = require('fs');
+const path = require('path');
+
+// Configuration
+const POLICY_CONFIG_PATH = path.resolve(__dirname, '../..', process.env.POLICY_CONFIG_PATH || 'config/policy.json');
+
+// Used when no policy file is present: pass the model's classification through unchanged
+const DEFAULT_POLICY = {
+  version: 'default',
+  thresholds: {},
+  uncertainty_band: null,
+  consumer_threshold: { min: 0.3, max: 0.9 },
+  denylist: [],
+  deployer_denylist: [],
+  allowlist: []
+};
+
+// Cache for loaded policy
+let cachedPolicy = null;
+
+/**
+ * Normalize an address for list lookups
+ * EVM addresses are case-insensitive; base58 (Solana) addresses are not
+ * @param {string} address - Contract address
+ * @returns {string}
+ */
+function normalizeAddress(address) {
+  return /^0x/i.test(address) ? address.toLowerCase() : address;
+}
+
+/**
+ * Check that a value is a finite number within [0, 1]
+ * @param {*} value
+ * @returns {boolean}
+ */
+function isScore(value) {
+  return typeof value === 'number' && Number.isFinite(value) && value >= 0 && value <= 1;
+}
+
+/**
+ * Validate policy file contents
+ * Throws on anything that would make rules misfire rather than guessing a fallback
+ * @param {Object} config - Parsed policy merged over DEFAULT_POLICY
+ */
+function validatePolicy(config) {
+  if (typeof config.version !== 'string' && typeof config.version !== 'number') {
+    throw new Error('version must be a string');
+  }
+
+  if (config.thresholds === null || typeof config.thresholds !== 'object' || Array.isArray(config.thresholds)) {
+    throw new Error('thresholds must be an object keyed by chain');
+  }
+  for (const [chain, cutoffs] of Object.entries(config.thresholds)) {
+    if (!cutoffs || !isScore(cutoffs.high) || !isScore(cutoffs.medium)) {
+      throw new Error(`thresholds.${chain} must have numeric high and medium between 0 and 1`);
+    }
+    if (cutoffs.medium > cutoffs.high) {
+      throw new Error(`thresholds.${chain}.medium must not exceed high`);
+    }
+  }
+
+  const band = config.uncertainty_band;
+  if (band !== null) {
+    if (typeof band !== 'object' || !isScore(band.min) || !isScore(band.max)) {
+      throw new Error('uncertainty_band must be null or have numeric min and max between 0 and 1');
+    }
+    if (band.min >= band.max) {
+      throw new Error('uncertainty_band.min must be less than max');
+    }
+  }
+
+  const bounds = config.consumer_threshold;
+  if (!bounds || typeof bounds !== 'object' || !isScore(bounds.min) || !isScore(bounds.max)) {
+    throw new Error('consumer_threshold must have numeric min and max between 0 and 1');
+  }
+  if (bounds.min > bounds.max) {
+    throw new Error('consumer_threshold.min must not exceed max');
+  }
+
+  for (const list of ['denylist', 'deployer_denylist', 'allowlist']) {
+    if (!Array.isArray(config[list]) || config[list].some(address => typeof address !== 'string')) {
+      throw new Error(`${list} must be an array of address strings`);
+    }
+  }
+}
+
+/**
+ * Load risk policy from disk (cached)
+ * @returns {{version: string, thresholds: Object, uncertaintyBand: ?{min: number, max: number}, consumerThreshold: {min: number, max: number}, denylist: Set<string>, deployerDenylist: Set<string>, allowlist: Set<string>}}
+ */
+function loadPolicy() {
+  if (cachedPolicy) {
+    return cachedPolicy;
+  }
+
+  let config = DEFAULT_POLICY;
+  try {
+    config = { ...DEFAULT_POLICY, ...JSON.parse(fs.readFileSync(POLICY_CONFIG_PATH, 'utf8')) };
+    validatePolicy(config);
+    console.log(`[Policy] Loaded policy ${config.version} from ${POLICY_CONFIG_PATH}`);
+  } catch (error) {
+    if (error.code !== 'ENOENT') {
+      // A broken policy file must not silently downgrade to model-only decisions
+      throw new Error(`Failed to load risk policy: ${error.message}`);
+    }
+    console.warn(`[Policy] No policy file at ${POLICY_CONFIG_PATH}, using model classification as-is`);
+  }
+
+  cachedPolicy = {
+    version: String(config.version),
+    thresholds: config.thresholds,
+    uncertaintyBand: config.uncertainty_band,
+    consumerThreshold: config.consumer_threshold,
+    denylist: new Set(config.denylist.map(normalizeAddress)),
+    deployerDenylist: new Set(config.deployer_denylist.map(normalizeAddress)),
+    allowlist: new Set(config.allowlist.map(normalizeAddress))
+  };
+  return cachedPolicy;
+}
+
+/**
+ * Classify a risk score against high/medium cutoffs
+ * @param {number} riskScore - Score in [0, 1]
+ * @param {{high: number, medium: number}} thresholds - Lower bounds for each category
+ * @returns {string} - 'high', 'medium' or 'low'
+ */
+function classify(riskScore, thresholds) {
+  if (riskScore >= thresholds.high) return 'high';
+  if (riskScore >= thresholds.medium) return 'medium';
+  return 'low';
+}
+
+/**
+ * Validate a consumer-supplied decision threshold against the policy bounds
+ * @param {*} threshold - Value from the request
+ * @returns {?string} - Error message, or null if the threshold is acceptable
+ */
+function validateThreshold(threshold) {
+  const { min, max } = loadPolicy().consumerThreshold;
+  if (typeof threshold !== 'number' || !Number.isFinite(threshold)) {
+    return 'Invalid threshold. Expected a number.';
+  }
+  if (threshold < min || threshold > max) {
+    return `Invalid threshold. Must be between ${min} and ${max}.`;
+  }
+  return null;
+}
+
+/**
+ * Apply the risk policy to a model result
+ * Rules are evaluated in order: denylists (policy file contracts and deployers, then curated lists),
+ * allowlist, per-chain thresholds, model passthrough; scores inside the
+ * uncertainty band are then reported as 'inconclusive'. That is the base rule.
+ * An explicit consumer threshold is applied last and takes precedence over the
+ * band. Neither the band nor the threshold applies when an override list matched
+ * @param {{riskScore: number, riskCategory: string}} analysis - Raw model output
+ * @param {{contractAddress: string, blockchain: string, creator?: ?string, denylistMatches?: Array<{list: string}>, threshold?: number}} context - What was analyzed
+ * @returns {{riskCategory: string, rule: string, version: string, uncertaintyBand: ?{min: number, max: number}, threshold: ?number, consumerAdjustment: ?{from: string, to: string}}}
+ */
+function applyPolicy(analysis, { contractAddress, blockchain, creator = null, denylistMatches = [], threshold = null }) {
+  const policy = loadPolicy();
+  const address = normalizeAddress(contractAddress);
+  const chain = blockchain.toLowerCase();
+
+  let riskCategory = analysis.riskCategory;
+  let rule = 'model';
+
+  if (policy.denylist.has(address)) {
+    riskCategory = 'high';
+    rule = 'denylist';
+  } else if (creator && policy.deployerDenylist.has(normalizeAddress(creator))) {
+    riskCategory = 'high';
+    rule = 'deployer_denylist';
+  } else if (denylistMatches.length > 0) {
+    riskCategory = 'high';
+    rule = `denylist:${denylistMatches[0].list}`;
+  } else if (policy.allowlist.has(address)) {
+    riskCategory = 'low';
+    rule = 'allowlist';
+  } else if (policy.thresholds[chain]) {
+    riskCategory = classify(analysis.riskScore, policy.thresholds[chain]);
+    rule = `thresholds:${chain}`;
+  }
+
+  const isOverride = rule === 'allowlist' || rule.startsWith('denylist') || rule === 'deployer_denylist';
+  const classified = riskCategory;
+
+  const band = policy.uncertaintyBand;
+  if (band && !isOverride && analysis.riskScore >= band.min && analysis.riskScore < band.max) {
+    riskCategory = 'inconclusive';
+    rule = 'uncertainty_band';
+  }
+
+  let consumerAdjustment = null;
+  if (threshold !== null && !isOverride) {
+    // Consumer cutoff replaces the high-risk boundary (and the band); medium/low below it are kept
+    let adjusted = classified;
+    if (analysis.riskScore >= threshold) {
+      adjusted = 'high';
+    } else if (adjusted === 'high') {
+      adjusted = 'medium';
+    }
+    consumerAdjustment = { from: riskCategory, to: adjusted };
+    riskCategory = adjusted;
+  }
+
+  return {
+    riskCategory,
+    rule,
+    version: policy.version,
+    uncertaintyBand: band,
+    threshold,
+    consumerAdjustment
+  };
+}
+
+module.exports = {
+  applyPolicy,
+  loadPolicy,
+  validateThreshold,
+  normalizeAddress
+};
@@ -23,7 +23,9 @@ let cachedSession = null;
  * Calls Python script as subprocess
  * @param {string} contractAddress - Contract address (0x...)
  * @param {string} blockchain - Blockchain name (ethereum, bsc, polygon)
- * @returns {Promise<Object>} - 60 features as key-value pairs
+ * @returns {Promise<{features: Object, creator: ?string, simulated: boolean, imputed: boolean, imputationReason: ?string}>} - 60 features as key-value pairs,
+ *   the contract creator if known, whether the features are simulated, and whether any of them
+ *   are simulated or defaults because a data source (RPC, explorer) failed
  */
 async function extractFeatures(contractAddress, blockchain) {
   return new Promise((resolve, reject) => {
@@ -33,7 +35,8 @@ async function extractFeatures(contractAddress, blockchain) {
     const pythonProcess = spawn(PYTHON_PATH, [
       FEATURE_EXTRACTOR_PATH,
       contractAddress,
-      blockchain
+      blockchain,
+      '--with-metadata'
     ]);
 
     let stdout = '';
@@ -60,9 +63,26 @@ async function extractFeatures(contractAddress, blockchain) {
 
       try {
         // Parse JSON output
-        const features = JSON.parse(stdout);
+        const { features, metadata = {} } = JSON.parse(stdout);
         console.log(`[RugDetector] Successfully extracted ${Object.keys(features).length} features`);
-        resolve(features);
+        const simulated = metadata.source !== 'real';
+        const degradedSources = metadata.degraded_sources || [];
+        let imputationReason = null;
+        if (simulated) {
+          imputationReason = metadata.fallback_reason || 'Feature extraction ran in simulated mode';
+          console.warn(`[RugDetector] Features are simulated, not extracted: ${imputationReason}`);
+        } else if (degradedSources.length > 0) {
+          // The extractor swallows RPC/explorer errors and substitutes empty values
+          imputationReason = `Data sources unavailable: ${degradedSources.join(', ')}`;
+          console.warn(`[RugDetector] Some features are defaults: ${imputationReason}`);
+        }
+        resolve({
+          features,
+          creator: metadata.creator || null,
+          simulated,
+          imputed: imputationReason !== null,
+          imputationReason
+        });
       } catch (parseError) {
         console.error(`[RugDetector] Failed to parse Python output:`, stdout);
         reject(new Error('Failed to parse feature extraction output'));
@@ -287,46 +307,82 @@ function convertFeaturesToArray(features) {
   return featureArray;
 }
 
+// Map from the 60 extracted features to the 18 zkML features
+// Order must match training. Each entry is either derived from an extracted
+// feature (optionally multiplied or divided, falling back to `fallback` when
+// that yields a falsy value) or a fixed `estimate` because nothing is
+// extracted for it yet
+const ZKML_FEATURE_MAP = [
+  { name: 'mint_count_per_week', from: 'avgDailyTransactions', fallback: 0 },  // Proxy
+  { name: 'burn_count_per_week', from: 'avgDailyTransactions', multiplier: 0.5, fallback: 0 },  // Proxy
+  { name: 'mint_ratio', from: 'liquidityRatio', fallback: 0.5 },  // Proxy
+  { name: 'swap_ratio', from: 'transactionVelocity', divisor: 100, fallback: 0.3 },  // Proxy
+  { name: 'burn_ratio', estimate: 0.2 },
+  { name: 'mint_mean_period', estimate: 3.5 },
+  { name: 'swap_mean_period', estimate: 2.0 },
+  { name: 'burn_mean_period', estimate: 4.0 },
+  { name: 'swap_in_per_week', from: 'avgDailyTransactions', multiplier: 10, fallback: 1000 },
+  { name: 'swap_out_per_week', from: 'avgDailyTransactions', multiplier: 8, fallback: 800 },
+  { name: 'swap_rate', from: 'transactionVelocity', divisor: 10, fallback: 15.5 },
+  { name: 'lp_avg', from: 'liquidityPoolSize', fallback: 50000 },
+  { name: 'lp_std', from: 'liquidityPoolSize', multiplier: 0.1, fallback: 5000 },
+  { name: 'lp_creator_holding_ratio', from: 'top10HoldersPercent', divisor: 100, fallback: 0.1 },
+  { name: 'number_of_holders', from: 'holderCount', fallback: 20 },  // Proxy
+  { name: 'creator_balance_in_lp', from: 'ownerBalance', fallback: 0.05 },
+  { name: 'token_age_weeks', from: 'contractAge', divisor: 7, fallback: 30 },
+  { name: 'token_creator_holding_ratio', from: 'ownerBalance', fallback: 0.15 }
+];
+
+// Used in place of the whole vector when extraction fails
+const DEFAULT_ZKML_FEATURES = [100, 50, 0.5, 0.3, 0.2, 3.5, 2.0, 4.0, 1000, 800, 15.5, 50000, 5000, 0.1, 20, 0.05, 30, 0.15];
+
 /**
  * Extract 18 Uniswap V2-style features for zkML model
  * Simplified feature extraction for Jolt-Atlas compatible model
  * @param {string} contractAddress - Contract address (0x...)
  * @param {string} blockchain - Blockchain name (ethereum, bsc, polygon)
- * @returns {Promise<Array<number>>} - 18 features as array
+ * @returns {Promise<{features: Array<number>, provenance: Object<string, string>, creator: ?string, imputed: boolean, imputationReason: ?string}>} - 18 features as array,
+ *   where each value came from ('extracted', 'simulated', 'estimated' or 'default'), the contract creator if known,
+ *   and whether the features are defaults/simulated rather than extracted
  */
 async function extractZkmlFeatures(contractAddress, blockchain) {
   try {
     // For now, extract comprehensive features and map to zkML subset
-    const fullFeatures = await extractFeatures(contractAddress, blockchain);
-
-    // Map 60 features to 18 zkML features
-    // Order must match training: mint_count_per_week, burn_count_per_week, mint_ratio, swap_ratio, burn_ratio, etc.
-    const zkmlFeatures = [
-      fullFeatures.avgDailyTransactions || 0,  // Proxy for mint_count_per_week
-      fullFeatures.avgDailyTransactions * 0.5 || 0,  // Proxy for burn_count_per_week
-      fullFeatures.liquidityRatio || 0.5,  // mint_ratio proxy
-      fullFeatures.transactionVelocity / 100 || 0.3,  // swap_ratio proxy
-      0.2,  // burn_ratio - estimated
-      3.5,  // mint_mean_period - estimated
-      2.0,  // swap_mean_period - estimated
-      4.0,  // burn_mean_period - estimated
-      fullFeatures.avgDailyTransactions * 10 || 1000,  // swap_in_per_week
-      fullFeatures.avgDailyTransactions * 8 || 800,  // swap_out_per_week
-      fullFeatures.transactionVelocity / 10 || 15.5,  // swap_rate
-      fullFeatures.liquidityPoolSize || 50000,  // lp_avg
-      fullFeatures.liquidityPoolSize * 0.1 || 5000,  // lp_std
-      fullFeatures.top10HoldersPercent / 100 || 0.1,  // lp_creator_holding_ratio
-      fullFeatures.holderCount || 20,  // number_of_holders proxy
-      fullFeatures.ownerBalance || 0.05,  // creator_balance_in_lp
-      fullFeatures.contractAge / 7 || 30,  // token_age_weeks
-      fullFeatures.ownerBalance || 0.15  // token_creator_holding_ratio
-    ];
-
-    return zkmlFeatures;
+    const { features: fullFeatures, creator, simulated, imputed, imputationReason } = await extractFeatures(contractAddress, blockchain);
+
+    // Map 60 features to 18 zkML features, recording where each value came from
+    const zkmlFeatures = [];
+    const provenance = {};
+    for (const entry of ZKML_FEATURE_MAP) {
+      if (entry.estimate !== undefined) {
+        zkmlFeatures.push(entry.estimate);
+        provenance[entry.name] = 'estimated';
+        continue;
+      }
+
+      let value = fullFeatures[entry.from];
+      if (entry.multiplier !== undefined) value = value * entry.multiplier;
+      if (entry.divisor !== undefined) value = value / entry.divisor;
+      if (value) {
+        zkmlFeatures.push(value);
+        provenance[entry.name] = simulated ? 'simulated' : 'extracted';
+      } else {
+        zkmlFeatures.push(entry.fallback);
+        provenance[entry.name] = 'default';
+      }
+    }
+
+    return { features: zkmlFeatures, provenance, creator, imputed, imputationReason };
   } catch (error) {
     console.error(`[RugDetector] zkML feature extraction failed:`, error);
-    // Return default safe values
-    return [100, 50, 0.5, 0.3, 0.2, 3.5, 2.0, 4.0, 1000, 800, 15.5, 50000, 5000, 0.1, 20, 0.05, 30, 0.15];
+    // Return default safe values, flagged so callers know the score is not about this contract's data
+    return {
+      features: DEFAULT_ZKML_FEATURES.slice(),
+      provenance: Object.fromEntries(ZKML_FEATURE_MAP.map(entry => [entry.name, 'default'])),
+      creator: null,
+      imputed: true,
+      imputationReason: `Feature extraction failed: ${error.message}`
+    };
   }
 }
 
@@ -71,6 +71,14 @@ class BlockchainDataFetcher:
         # Cache for API responses
         self._cache = {}
 
+        # Calls that failed and were replaced by empty defaults
+        self.degraded_sources = []
+
+    def _mark_degraded(self, source: str):
+        """Record that a data source failed so callers can flag the features"""
+        if source not in self.degraded_sources:
+            self.degraded_sources.append(source)
+
     def _api_call(self, params: dict, cache_key: str = None) -> dict:
         """Make API call to blockchain explorer with caching"""
         if cache_key and cache_key in self._cache:
@@ -87,12 +95,19 @@ class BlockchainDataFetcher:
             response.raise_for_status()
             data = response.json()
 
+            # Explorers report errors (bad key, rate limit) as NOTOK rather than an HTTP status
+            if data.get('status') == '0' and str(data.get('message', '')).startswith('NOTOK'):
+                print(f"API call failed: {data.get('result')}", file=sys.stderr)
+                self._mark_degraded(f"explorer:{params.get('action')}")
+                return data
+
             if cache_key:
                 self._cache[cache_key] = data
 
             return data
         except Exception as e:
             print(f"API call failed: {e}", file=sys.stderr)
+            self._mark_degraded(f"explorer:{params.get('action')}")
             return {'status': '0', 'result': []}
 
     def get_contract_code(self) -> Tuple[str, dict]:
@@ -123,6 +138,7 @@ class BlockchainDataFetcher:
             return bytecode, source_info
         except Exception as e:
             print(f"Failed to get contract code: {e}", file=sys.stderr)
+            self._mark_degraded('rpc:contract_code')
             return '0x', {}
 
     def get_contract_creation_info(self) -> dict:
@@ -155,6 +171,7 @@ class BlockchainDataFetcher:
             return {}
         except Exception as e:
             print(f"Failed to get creation info: {e}", file=sys.stderr)
+            self._mark_degraded('rpc:creation_info')
             return {}
 
     def get_token_info(self) -> dict:
@@ -232,6 +249,7 @@ class BlockchainDataFetcher:
             return 0
         except Exception as e:
             print(f"Failed to get holder count: {e}", file=sys.stderr)
+            self._mark_degraded('holder_count')
             return 0
 
     def get_transaction_count(self) -> int:
@@ -299,6 +317,7 @@ class BlockchainDataFetcher:
             }
         except Exception as e:
             print(f"Failed to analyze transactions: {e}", file=sys.stderr)
+            self._mark_degraded('recent_transactions')
             return {}
 
 
@@ -363,13 +382,15 @@ def analyze_contract_code(bytecode: str, source_info: dict) -> dict:
     return features
 
 
-def extract_features_real(contract_address: str, blockchain: str = 'ethereum') -> dict:
+def extract_features_real(contract_address: str, blockchain: str = 'ethereum', metadata: dict = None) -> dict:
     """
     Extract 60 real features from blockchain data
 
     Args:
         contract_address: Contract address (0x...)
         blockchain: Blockchain name (ethereum, bsc, polygon)
+        metadata: Optional dict filled with non-feature context ('creator', 'source',
+            'degraded_sources')
 
     Returns:
         dict: 60 features as key-value pairs
@@ -387,6 +408,13 @@ def extract_features_real(contract_address: str, blockchain: str = 'ethereum') -
     creation_info = fetcher.get_contract_creation_info()
     token_info = fetcher.get_token_info()
 
+    # Non-feature context for the caller (e.g. deployer denylist checks)
+    if metadata is not None:
+        metadata['creator'] = creation_info.get('creator')
+        metadata['source'] = 'real'
+        # Same list object, so failures in later fetches are reported too
+        metadata['degraded_sources'] = fetcher.degraded_sources
+
     # ===== STEP 2: Calculate contract age =====
     contract_age_days = 0
     if creation_info.get('timestamp'):
@@ -557,7 +585,7 @@ def extract_features_real(contract_address: str, blockchain: str = 'ethereum') -
     return features
 
 
-def extract_features_hybrid(contract_address: str, blockchain: str = 'ethereum') -> dict:
+def extract_features_hybrid(contract_address: str, blockchain: str = 'ethereum', metadata: dict = None) -> dict:
     """
     Extract features using hybrid approach:
     - Real data where available
@@ -565,28 +593,37 @@ def extract_features_hybrid(contract_address: str, blockchain: str = 'ethereum')
     """
     try:
         # Try real extraction first
-        return extract_features_real(contract_address, blockchain)
+        return extract_features_real(contract_address, blockchain, metadata)
     except Exception as e:
         print(f"Real extraction failed: {e}, falling back to simulated", file=sys.stderr)
         # Fallback to simulated if real extraction fails
         from extract_features_simulated import extract_features as extract_features_simulated
+        if metadata is not None:
+            metadata['source'] = 'simulated'
+            metadata['fallback_reason'] = str(e)
         return extract_features_simulated(contract_address, blockchain)
 
 
-def extract_features(contract_address: str, blockchain: str = 'ethereum') -> dict:
+def extract_features(contract_address: str, blockchain: str = 'ethereum', metadata: dict = None) -> dict:
     """
     Main entry point for feature extraction
     Delegates to appropriate extractor based on FEATURE_EXTRACTION_MODE
+
+    If a metadata dict is passed, it is filled with non-feature context:
+    the contract creator, whether features are 'real' or 'simulated', and
+    which data sources failed during real extraction ('degraded_sources')
     """
     mode = FEATURE_EXTRACTION_MODE.lower()
 
     if mode == 'real':
-        return extract_features_real(contract_address, blockchain)
+        return extract_features_real(contract_address, blockchain, metadata)
     elif mode == 'simulated':
         from extract_features_simulated import extract_features as extract_features_simulated
+        if metadata is not None:
+            metadata['source'] = 'simulated'
         return extract_features_simulated(contract_address, blockchain)
     else:  # hybrid (default)
-        return extract_features_hybrid(contract_address, blockchain)
+        return extract_features_hybrid(contract_address, blockchain, metadata)
 
 
 def validate_contract_address(address: str) -> str:
@@ -611,12 +648,16 @@ def validate_contract_address(address: str) -> str:
 
 def main():
     """Main entry point when called as script"""
-    if len(sys.argv) < 2:
+    # --with-metadata wraps output as {"features": {...}, "metadata": {...}}
+    with_metadata = '--with-metadata' in sys.argv
+    args = [arg for arg in sys.argv[1:] if arg != '--with-metadata']
+
+    if len(args) < 1:
         print(json.dumps({"error": "Missing contract address argument"}), file=sys.stderr)
         sys.exit(1)
 
-    contract_address = sys.argv[1]
-    blockchain = sys.argv[2] if len(sys.argv) > 2 else 'ethereum'
+    contract_address = args[0]
+    blockchain = args[1] if len(args) > 1 else 'ethereum'
 
     try:
         # Validate inputs
@@ -626,10 +667,14 @@ def main():
             raise ValueError(f"Unsupported blockchain: {blockchain}")
 
         # Extract features
-        features = extract_features(contract_address, blockchain)
+        metadata = {'creator': None, 'source': None, 'degraded_sources': []}
+        features = extract_features(contract_address, blockchain, metadata)
 
         # Output as JSON to stdout
-        print(json.dumps(features, indent=2))
+        if with_metadata:
+            print(json.dumps({'features': features, 'metadata': metadata}, indent=2))
+        else:
+            print(json.dumps(features, indent=2))
 
     except Exception as e:
         print(json.dumps({"error": str(e)}), file=sys.stderr)
@@ -159,13 +159,18 @@ function updateRiskScore(score, category) {
     } else if (category === 'high') {
         stops[0].setAttribute('stop-color', '#ff3366');
         stops[1].setAttribute('stop-color', '#ff6b9d');
+    } else if (category === 'inconclusive') {
+        stops[0].setAttribute('stop-color', '#a0a0a0');
+        stops[1].setAttribute('stop-color', '#666666');
     }
 }
 
 // Update risk badge
 function updateRiskBadge(category) {
     riskBadge.className = `risk-badge ${category}`;
-    riskBadge.querySelector('.badge-text').textContent = capitalizeFirst(category) + ' Risk';
+    riskBadge.querySelector('.badge-text').textContent = category === 'inconclusive'
+        ? 'Inconclusive'
+        : capitalizeFirst(category) + ' Risk';
 }
 
 // Display features